@@ -1,9 +1,10 @@
 use crate::logs::{emit_stack, PlatformFeeLog, ReferrerFeeLog};
-use crate::utils::{read_u64, read_u8};
+use crate::utils::{format_token_amount, read_u64, read_u8};
 use solana_program::account_info::AccountInfo;
 use solana_program::entrypoint::ProgramResult;
 use solana_program::program::invoke;
 use solana_program::program_error::ProgramError;
+use solana_program::program_pack::Pack;
 use std::cmp::min;
 
 /// transfers autobahn-executor fee to platform_fee_account and optionally referrer_fee_account
@@ -12,19 +13,48 @@ use std::cmp::min;
 /// Data:
 /// - total_fee_amount_native: u64
 /// - platform_fee_percent: u8
+/// - min_fee_native: u64 (0 means no floor)
+/// - max_fee_native: u64 (0 means no cap)
 ///
 /// If there is a referrer
-/// - Platform will get `platform_fee_percent/100 * total_fee_amount_native`
-/// - Referrer will get  `(1 - platform_fee_percent/100) * total_fee_amount_native`
+/// - The percentage fee is `platform_fee_percent/100 * total_fee_amount_native`, clamped to
+///   `[min_fee_native, max_fee_native]` (an unset bound, i.e. 0, leaves that side open), with
+///   both bounds further capped at `total_fee_amount_native` so the platform can never take
+///   more than the fee being charged
+/// - Platform will get the clamped fee
+/// - Referrer will get `total_fee_amount_native - clamped_fee`
 ///
 /// If there is no referrer,
-/// - Platform will get `total_fee_amount_native`
+/// - Platform will get `total_fee_amount_native` (the floor/cap only bound the percentage
+///   fee used in a split, so they have no effect here)
+///
+/// Accounts layout
+/// - token_program
+/// - token_account
+/// - platform_fee_account
+/// - signer
+/// - referrer_fee_account (optional)
+/// - mint
+///
+/// `mint` is read for its `decimals` so the emitted logs can carry a human-readable amount
+/// alongside the raw native one.
 pub fn execute_charge_fees(accounts: &[AccountInfo], instruction_data: &[u8]) -> ProgramResult {
     let (fee_amount, instruction_data) = read_u64(instruction_data);
-    let (platform_fee_percent, _) = read_u8(instruction_data);
+    let (platform_fee_percent, instruction_data) = read_u8(instruction_data);
     let platform_fee_percent = min(100, platform_fee_percent);
+    let (min_fee_native, instruction_data) = read_u64(instruction_data);
+    let (max_fee_native, _) = read_u64(instruction_data);
+    let max_fee_native = if max_fee_native == 0 {
+        u64::MAX
+    } else {
+        max_fee_native
+    };
+    // never let the floor/cap push the platform fee past the fee actually being charged
+    let min_fee_native = min(min_fee_native, fee_amount);
+    let max_fee_native = min(max_fee_native, fee_amount);
+    let min_fee_native = min(min_fee_native, max_fee_native);
 
-    if accounts.len() < 4 {
+    if accounts.len() < 5 {
         return Err(ProgramError::NotEnoughAccountKeys);
     }
 
@@ -33,9 +63,15 @@ pub fn execute_charge_fees(accounts: &[AccountInfo], instruction_data: &[u8]) ->
     let platform_fee_account = &accounts[2];
     let signer_account = &accounts[3];
 
-    let has_referrer = accounts.len() == 5;
+    let has_referrer = accounts.len() == 6;
+    let mint_account = if has_referrer {
+        &accounts[5]
+    } else {
+        &accounts[4]
+    };
     let platform_fee_amount = if has_referrer {
-        (fee_amount * platform_fee_percent as u64) / 100
+        let percentage_fee = (fee_amount * platform_fee_percent as u64) / 100;
+        percentage_fee.clamp(min_fee_native, max_fee_native)
     } else {
         fee_amount
     };
@@ -45,6 +81,13 @@ pub fn execute_charge_fees(accounts: &[AccountInfo], instruction_data: &[u8]) ->
         return Err(ProgramError::IncorrectProgramId);
     }
 
+    // verify the mint is actually owned by the token program before trusting its decimals
+    if !spl_token::ID.eq(mint_account.owner) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let decimals = spl_token::state::Mint::unpack(&mint_account.data.borrow())?.decimals;
+
     let ix = spl_token::instruction::transfer(
         token_program.key,
         token_account.key,
@@ -60,6 +103,7 @@ pub fn execute_charge_fees(accounts: &[AccountInfo], instruction_data: &[u8]) ->
         user: *signer_account.key,
         platform_token_account: *platform_fee_account.key,
         platform_fee: platform_fee_amount,
+        platform_fee_ui: format_token_amount(platform_fee_amount, decimals),
     })?;
 
     if has_referrer {
@@ -88,6 +132,7 @@ pub fn execute_charge_fees(accounts: &[AccountInfo], instruction_data: &[u8]) ->
             referee: *signer_account.key,
             referer_token_account: *referrer_fee_account.key,
             referrer_fee: referrer_fee_amount,
+            referrer_fee_ui: format_token_amount(referrer_fee_amount, decimals),
         })?;
     }
 
@@ -0,0 +1,121 @@
+use crate::logs::{emit_stack, PlatformFeeLog, ReferrerFeeLog};
+use crate::utils::{format_token_amount, read_u16, read_u64};
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program::invoke;
+use solana_program::program_error::ProgramError;
+use solana_program::program_pack::Pack;
+use std::cmp::min;
+
+/// transfers autobahn-executor fee to platform_fee_account and optionally referrer_fee_account
+///
+/// Instruction data layout
+/// Data:
+/// - total_fee_amount_native: u64
+/// - platform_fee_bps: u16 (clamped to 0..=10_000)
+///
+/// If there is a referrer
+/// - Platform will get `platform_fee_bps/10_000 * total_fee_amount_native`
+/// - Referrer will get `(1 - platform_fee_bps/10_000) * total_fee_amount_native`
+///
+/// If there is no referrer,
+/// - Platform will get `total_fee_amount_native`
+///
+/// Accounts layout
+/// - token_program
+/// - token_account
+/// - platform_fee_account
+/// - signer
+/// - referrer_fee_account (optional)
+/// - mint
+pub fn execute_charge_fees_bps(
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let (fee_amount, instruction_data) = read_u64(instruction_data);
+    let (platform_fee_bps, _) = read_u16(instruction_data);
+    let platform_fee_bps = min(10_000, platform_fee_bps);
+
+    if accounts.len() < 5 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let token_program = &accounts[0];
+    let token_account = &accounts[1];
+    let platform_fee_account = &accounts[2];
+    let signer_account = &accounts[3];
+
+    let has_referrer = accounts.len() == 6;
+    let mint_account = if has_referrer {
+        &accounts[5]
+    } else {
+        &accounts[4]
+    };
+    let platform_fee_amount = if has_referrer {
+        (fee_amount as u128 * platform_fee_bps as u128 / 10_000) as u64
+    } else {
+        fee_amount
+    };
+
+    // verify correct token program is passed
+    if !spl_token::ID.eq(token_program.key) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // verify the mint is actually owned by the token program before trusting its decimals
+    if !spl_token::ID.eq(mint_account.owner) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let decimals = spl_token::state::Mint::unpack(&mint_account.data.borrow())?.decimals;
+
+    let ix = spl_token::instruction::transfer(
+        token_program.key,
+        token_account.key,
+        platform_fee_account.key,
+        signer_account.key,
+        &[signer_account.key],
+        platform_fee_amount,
+    )?;
+
+    invoke(&ix, &accounts[1..4])?;
+
+    emit_stack(PlatformFeeLog {
+        user: *signer_account.key,
+        platform_token_account: *platform_fee_account.key,
+        platform_fee: platform_fee_amount,
+        platform_fee_ui: format_token_amount(platform_fee_amount, decimals),
+    })?;
+
+    if has_referrer {
+        let referrer_fee_account = &accounts[4];
+        let referrer_fee_amount = fee_amount.saturating_sub(platform_fee_amount);
+
+        let ix = spl_token::instruction::transfer(
+            token_program.key,
+            token_account.key,
+            referrer_fee_account.key,
+            signer_account.key,
+            &[signer_account.key],
+            referrer_fee_amount,
+        )?;
+
+        invoke(
+            &ix,
+            &[
+                accounts[1].clone(),
+                accounts[4].clone(),
+                accounts[3].clone(),
+            ],
+        )?;
+
+        emit_stack(ReferrerFeeLog {
+            referee: *signer_account.key,
+            referer_token_account: *referrer_fee_account.key,
+            referrer_fee: referrer_fee_amount,
+            referrer_fee_ui: format_token_amount(referrer_fee_amount, decimals),
+        })?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,163 @@
+use crate::logs::{emit_stack, PlatformFeeLog, ReferrerFeeLog};
+use crate::utils::{format_token_amount, read_u16, read_u64, read_u8};
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program::invoke;
+use solana_program::program_error::ProgramError;
+use solana_program::program_pack::Pack;
+
+/// transfers autobahn-executor fee across an arbitrary number of recipients (platform,
+/// referrers, affiliates, ...) in a single atomic instruction
+///
+/// Instruction data layout
+/// Data:
+/// - total_fee_amount_native: u64
+/// - recipient_count: u8 (n)
+/// - weights_bps: [u16; n], each a basis-point share, summing to <= 10_000
+///
+/// Accounts layout
+/// - token_program
+/// - token_account
+/// - signer
+/// - destination_token_account * n
+/// - mint
+///
+/// Each recipient i receives `floor(total_fee_amount_native * weights_bps[i] / 10_000)`.
+/// Whatever is left of `total_fee_amount_native` after those shares (the rounding remainder
+/// of the floor division, plus the unweighted share when the weights sum to less than
+/// 10_000) is assigned to recipient 0 (the platform account), so the whole fee is transferred.
+pub fn execute_charge_fees_distribute(
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let (fee_amount, instruction_data) = read_u64(instruction_data);
+    let (recipient_count, mut instruction_data) = read_u8(instruction_data);
+    let recipient_count = recipient_count as usize;
+
+    if accounts.len() < 4 + recipient_count {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let token_program = &accounts[0];
+    let token_account = &accounts[1];
+    let signer_account = &accounts[2];
+    let mint_account = &accounts[3 + recipient_count];
+
+    // verify correct token program is passed
+    if !spl_token::ID.eq(token_program.key) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // verify the mint is actually owned by the token program before trusting its decimals
+    if !spl_token::ID.eq(mint_account.owner) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let decimals = spl_token::state::Mint::unpack(&mint_account.data.borrow())?.decimals;
+
+    let mut weights_bps = Vec::with_capacity(recipient_count);
+    let mut weights_sum: u32 = 0;
+    for _ in 0..recipient_count {
+        let (weight_bps, rest) = read_u16(instruction_data);
+        instruction_data = rest;
+        weights_sum += weight_bps as u32;
+        weights_bps.push(weight_bps);
+    }
+
+    if weights_sum > 10_000 {
+        return Err(ProgramError::InvalidInstructionData);
+    }
+
+    let amounts = split_amounts(fee_amount, &weights_bps);
+
+    for (i, amount) in amounts.into_iter().enumerate() {
+        let destination_account = &accounts[3 + i];
+
+        let ix = spl_token::instruction::transfer(
+            token_program.key,
+            token_account.key,
+            destination_account.key,
+            signer_account.key,
+            &[signer_account.key],
+            amount,
+        )?;
+
+        invoke(
+            &ix,
+            &[
+                token_account.clone(),
+                destination_account.clone(),
+                signer_account.clone(),
+            ],
+        )?;
+
+        if i == 0 {
+            emit_stack(PlatformFeeLog {
+                user: *signer_account.key,
+                platform_token_account: *destination_account.key,
+                platform_fee: amount,
+                platform_fee_ui: format_token_amount(amount, decimals),
+            })?;
+        } else {
+            emit_stack(ReferrerFeeLog {
+                referee: *signer_account.key,
+                referer_token_account: *destination_account.key,
+                referrer_fee: amount,
+                referrer_fee_ui: format_token_amount(amount, decimals),
+            })?;
+        }
+    }
+
+    Ok(())
+}
+
+/// splits `fee_amount` across `weights_bps` via `floor(fee_amount * weight_i / 10_000)`,
+/// assigning everything left undistributed (rounding remainder and any unweighted share) to
+/// recipient 0 (the platform account)
+fn split_amounts(fee_amount: u64, weights_bps: &[u16]) -> Vec<u64> {
+    let mut amounts = Vec::with_capacity(weights_bps.len());
+    let mut distributed: u64 = 0;
+    for weight_bps in weights_bps {
+        let amount = (fee_amount as u128 * *weight_bps as u128 / 10_000) as u64;
+        distributed += amount;
+        amounts.push(amount);
+    }
+
+    if let Some(first_amount) = amounts.first_mut() {
+        *first_amount += fee_amount.saturating_sub(distributed);
+    }
+
+    amounts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_evenly_with_no_remainder() {
+        assert_eq!(split_amounts(10_000, &[5_000, 5_000]), vec![5_000, 5_000]);
+    }
+
+    #[test]
+    fn remainder_goes_to_recipient_zero() {
+        // 10 * 3333/10_000 = 3 (floor) for each, 9 distributed, 1 left over -> recipient 0
+        assert_eq!(split_amounts(10, &[3_333, 3_333, 3_333]), vec![4, 3, 3]);
+    }
+
+    #[test]
+    fn unweighted_remainder_also_goes_to_recipient_zero() {
+        // weights sum to less than 10_000; the 500 left unweighted goes to recipient 0 as well
+        assert_eq!(split_amounts(1_000, &[2_500, 2_500]), vec![750, 250]);
+    }
+
+    #[test]
+    fn empty_weights_returns_empty() {
+        assert_eq!(split_amounts(1_000, &[]), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn zero_fee_amount_splits_to_zero() {
+        assert_eq!(split_amounts(0, &[5_000, 5_000]), vec![0, 0]);
+    }
+}
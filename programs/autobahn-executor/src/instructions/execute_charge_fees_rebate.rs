@@ -0,0 +1,105 @@
+use crate::logs::{emit_stack, PlatformFeeLog, ReferrerFeeLog};
+use crate::utils::{format_token_amount, read_u16, read_u64};
+use solana_program::account_info::AccountInfo;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::program::invoke;
+use solana_program::program_error::ProgramError;
+use solana_program::program_pack::Pack;
+use std::cmp::min;
+
+/// transfers autobahn-executor fee to platform_fee_account and referrer_fee_account
+///
+/// Instruction data layout
+/// Data:
+/// - total_fee_amount_native: u64
+/// - rebate_bps: u16 (clamped to 0..=10_000)
+///
+/// - Referrer gets `rebate_bps/10_000 * total_fee_amount_native`
+/// - Platform gets `total_fee_amount_native - referrer_fee`
+///
+/// Accounts layout
+/// - token_program
+/// - token_account
+/// - platform_fee_account
+/// - signer
+/// - referrer_fee_account
+/// - mint
+pub fn execute_charge_fees_rebate(
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let (fee_amount, instruction_data) = read_u64(instruction_data);
+    let (rebate_bps, _) = read_u16(instruction_data);
+    let rebate_bps = min(10_000, rebate_bps);
+
+    if accounts.len() != 6 {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let token_program = &accounts[0];
+    let token_account = &accounts[1];
+    let platform_fee_account = &accounts[2];
+    let signer_account = &accounts[3];
+    let referrer_fee_account = &accounts[4];
+    let mint_account = &accounts[5];
+
+    // verify correct token program is passed
+    if !spl_token::ID.eq(token_program.key) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    // verify the mint is actually owned by the token program before trusting its decimals
+    if !spl_token::ID.eq(mint_account.owner) {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    let decimals = spl_token::state::Mint::unpack(&mint_account.data.borrow())?.decimals;
+
+    let referrer_fee_amount = (fee_amount as u128 * rebate_bps as u128 / 10_000) as u64;
+    let platform_fee_amount = fee_amount.saturating_sub(referrer_fee_amount);
+
+    let ix = spl_token::instruction::transfer(
+        token_program.key,
+        token_account.key,
+        platform_fee_account.key,
+        signer_account.key,
+        &[signer_account.key],
+        platform_fee_amount,
+    )?;
+
+    invoke(&ix, &accounts[1..4])?;
+
+    emit_stack(PlatformFeeLog {
+        user: *signer_account.key,
+        platform_token_account: *platform_fee_account.key,
+        platform_fee: platform_fee_amount,
+        platform_fee_ui: format_token_amount(platform_fee_amount, decimals),
+    })?;
+
+    let ix = spl_token::instruction::transfer(
+        token_program.key,
+        token_account.key,
+        referrer_fee_account.key,
+        signer_account.key,
+        &[signer_account.key],
+        referrer_fee_amount,
+    )?;
+
+    invoke(
+        &ix,
+        &[
+            accounts[1].clone(),
+            accounts[4].clone(),
+            accounts[3].clone(),
+        ],
+    )?;
+
+    emit_stack(ReferrerFeeLog {
+        referee: *signer_account.key,
+        referer_token_account: *referrer_fee_account.key,
+        referrer_fee: referrer_fee_amount,
+        referrer_fee_ui: format_token_amount(referrer_fee_amount, decimals),
+    })?;
+
+    Ok(())
+}
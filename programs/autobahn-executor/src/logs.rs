@@ -0,0 +1,44 @@
+use borsh::BorshSerialize;
+use solana_program::entrypoint::ProgramResult;
+use solana_program::log::sol_log_data;
+use solana_program::pubkey::Pubkey;
+
+pub trait AutobahnLog: BorshSerialize {
+    const LOG_TYPE: u8;
+}
+
+/// serializes `log` with a leading discriminant byte and writes it to the program log
+/// via `sol_log_data`, so off-chain indexers can decode it without parsing text output
+pub fn emit_stack<T: AutobahnLog>(log: T) -> ProgramResult {
+    let mut data = Vec::with_capacity(256);
+    data.push(T::LOG_TYPE);
+    log.serialize(&mut data)?;
+    sol_log_data(&[&data]);
+    Ok(())
+}
+
+#[derive(BorshSerialize)]
+pub struct PlatformFeeLog {
+    pub user: Pubkey,
+    pub platform_token_account: Pubkey,
+    pub platform_fee: u64,
+    /// `platform_fee` rendered in the mint's decimal units, e.g. "1.5"
+    pub platform_fee_ui: String,
+}
+
+impl AutobahnLog for PlatformFeeLog {
+    const LOG_TYPE: u8 = 0;
+}
+
+#[derive(BorshSerialize)]
+pub struct ReferrerFeeLog {
+    pub referee: Pubkey,
+    pub referer_token_account: Pubkey,
+    pub referrer_fee: u64,
+    /// `referrer_fee` rendered in the mint's decimal units, e.g. "1.5"
+    pub referrer_fee_ui: String,
+}
+
+impl AutobahnLog for ReferrerFeeLog {
+    const LOG_TYPE: u8 = 1;
+}
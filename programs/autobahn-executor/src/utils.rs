@@ -0,0 +1,71 @@
+pub fn read_u8(data: &[u8]) -> (u8, &[u8]) {
+    (data[0], &data[1..])
+}
+
+pub fn read_u16(data: &[u8]) -> (u16, &[u8]) {
+    let value = u16::from_le_bytes(data[0..2].try_into().unwrap());
+    (value, &data[2..])
+}
+
+pub fn read_u64(data: &[u8]) -> (u64, &[u8]) {
+    let value = u64::from_le_bytes(data[0..8].try_into().unwrap());
+    (value, &data[8..])
+}
+
+/// renders a raw token amount in the mint's decimal units, e.g. `format_token_amount(1_500_000, 6)`
+/// returns `"1.5"`
+pub fn format_token_amount(amount: u64, decimals: u8) -> String {
+    let decimals = decimals as usize;
+    let digits = amount.to_string();
+    let padded = format!("{:0>width$}", digits, width = decimals + 1);
+
+    let split_at = padded.len() - decimals;
+    let (whole, fractional) = padded.split_at(split_at);
+
+    if decimals == 0 {
+        return whole.to_string();
+    }
+
+    let fractional = fractional.trim_end_matches('0');
+    if fractional.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, fractional)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trims_trailing_zeros() {
+        assert_eq!(format_token_amount(1_500_000, 6), "1.5");
+    }
+
+    #[test]
+    fn trims_dangling_decimal_point() {
+        assert_eq!(format_token_amount(1_000_000, 6), "1");
+    }
+
+    #[test]
+    fn zero_decimals_returns_integer() {
+        assert_eq!(format_token_amount(42, 0), "42");
+    }
+
+    #[test]
+    fn zero_amount() {
+        assert_eq!(format_token_amount(0, 6), "0");
+        assert_eq!(format_token_amount(0, 0), "0");
+    }
+
+    #[test]
+    fn amount_shorter_than_decimals_is_left_padded() {
+        assert_eq!(format_token_amount(5, 6), "0.000005");
+    }
+
+    #[test]
+    fn keeps_nonzero_fractional_digits() {
+        assert_eq!(format_token_amount(1_234_567, 6), "1.234567");
+    }
+}